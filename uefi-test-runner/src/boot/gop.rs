@@ -0,0 +1,41 @@
+//! Graphics Output Protocol test.
+//!
+//! Only compiled in when the `gop` feature is enabled, which `xtask run`
+//! turns on whenever `--graphics-device` selects an actual display adapter
+//! (see `xtask::cargo::Feature::Gop`), since not every CI runner can render
+//! a framebuffer.
+
+use uefi::proto::console::gop::GraphicsOutput;
+use uefi::table::boot::BootServices;
+
+/// Opens the Graphics Output Protocol and checks that the firmware actually
+/// switched to the framebuffer resolution `xtask run --graphics-resolution`
+/// requested, so the QEMU-side `-device`/`-vga`/`-g` flags are verified to
+/// have taken effect rather than just trusting QEMU accepted them.
+pub fn test(bt: &BootServices, want_resolution: (usize, usize)) {
+    info!("Running Graphics Output Protocol test");
+
+    let handle = bt
+        .get_handle_for_protocol::<GraphicsOutput>()
+        .expect("failed to get GOP handle");
+    let mut gop = bt
+        .open_protocol_exclusive::<GraphicsOutput>(handle)
+        .expect("failed to open GOP");
+
+    let mode = gop.current_mode_info();
+    let got_resolution = mode.resolution();
+    assert_eq!(
+        got_resolution, want_resolution,
+        "GOP resolution {:?} doesn't match the {:?} requested on the QEMU command line",
+        got_resolution, want_resolution
+    );
+
+    let mut fb = gop.frame_buffer();
+    assert!(fb.size() > 0, "framebuffer should be non-empty");
+
+    // Write the first byte of the framebuffer, to exercise the write path
+    // rather than just the mode-negotiation path.
+    unsafe {
+        fb.write_byte(0, 0xff);
+    }
+}
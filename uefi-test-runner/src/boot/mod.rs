@@ -0,0 +1,4 @@
+//! Boot-services-backed tests.
+
+#[cfg(feature = "gop")]
+pub mod gop;
@@ -0,0 +1,20 @@
+//! A Rust library for developing UEFI applications and drivers.
+//!
+//! See the [crate-level documentation in the repository] for more details.
+//!
+//! [crate-level documentation in the repository]: https://github.com/rust-osdev/uefi-rs
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod data_types;
+pub mod proto;
+pub mod result;
+pub mod table;
+
+#[cfg(feature = "alloc")]
+pub mod std_support;
+
+pub use crate::data_types::{CStr16, Char16, Guid};
+pub use crate::result::{Error, Result, ResultExt, Status};
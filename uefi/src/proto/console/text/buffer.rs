@@ -0,0 +1,171 @@
+use super::output::{clamp_background, Color, Output};
+use crate::{CStr16, Char16, Error, Result, Status};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A single character cell in a [`Console`]'s screen buffer.
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    ch: Char16,
+    fg: Color,
+    bg: Color,
+}
+
+/// An in-memory character grid that sits in front of an [`Output`] device.
+///
+/// Every [`fmt::Write`] into a `Console` only updates the grid in memory
+/// and marks the affected rows dirty; no firmware call is made until
+/// [`flush`][Self::flush] is called. This turns a full-screen redraw from
+/// one `output_string` call per 128-character chunk into a handful of
+/// calls: one per contiguous run of same-colored cells in each dirty row,
+/// and none at all for rows that haven't changed.
+#[derive(Debug)]
+pub struct Console {
+    columns: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+    cursor: (usize, usize),
+    dirty_rows: Vec<bool>,
+    fg: Color,
+    bg: Color,
+}
+
+impl Console {
+    /// Creates a console buffer sized to `output`'s current text mode,
+    /// falling back to the mandatory 80x25 mode if none is set yet.
+    pub fn new(output: &Output) -> Result<Self> {
+        let (columns, rows) = match output.current_mode()? {
+            Some(mode) => (mode.columns(), mode.rows()),
+            None => (80, 25),
+        };
+
+        let blank = Cell {
+            ch: Char16::try_from(' ').unwrap(),
+            fg: Color::LightGray,
+            bg: Color::Black,
+        };
+
+        Ok(Self {
+            columns,
+            rows,
+            cells: vec![blank; columns * rows],
+            cursor: (0, 0),
+            dirty_rows: vec![false; rows],
+            fg: Color::LightGray,
+            bg: Color::Black,
+        })
+    }
+
+    /// Returns the cursor's current `(column, row)` position.
+    #[must_use]
+    pub const fn cursor_position(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Moves the write cursor, clamping it to the grid's bounds.
+    pub fn set_cursor_position(&mut self, column: usize, row: usize) {
+        self.cursor = (
+            column.min(self.columns.saturating_sub(1)),
+            row.min(self.rows.saturating_sub(1)),
+        );
+    }
+
+    /// Sets the foreground/background color used by subsequent writes.
+    ///
+    /// `bg` is downgraded to one of the first 8 [`Color`] variants if
+    /// needed, the same way [`AnsiOutput`](super::output::AnsiOutput) does,
+    /// since only those are legal backgrounds for [`Output::set_color`].
+    pub fn set_color(&mut self, fg: Color, bg: Color) {
+        self.fg = fg;
+        self.bg = clamp_background(bg);
+    }
+
+    /// Writes one character at the cursor and advances it, wrapping to the
+    /// start of the next row. Rows pushed past the bottom of the grid are
+    /// dropped rather than scrolled.
+    fn put_char(&mut self, ch: char) {
+        let (column, row) = self.cursor;
+
+        if ch == '\n' {
+            self.cursor = (0, row + 1);
+            return;
+        }
+
+        if row < self.rows {
+            if let Ok(ch) = Char16::try_from(ch) {
+                let index = row * self.columns + column;
+                self.cells[index] = Cell {
+                    ch,
+                    fg: self.fg,
+                    bg: self.bg,
+                };
+                self.dirty_rows[row] = true;
+            }
+        }
+
+        let mut column = column + 1;
+        let mut row = row;
+        if column >= self.columns {
+            column = 0;
+            row += 1;
+        }
+        self.cursor = (column, row);
+    }
+
+    /// Writes every dirty row to `output`, coalescing consecutive cells
+    /// that share the same foreground/background color into a single
+    /// `set_cursor_position` + `set_color` + `output_string` sequence. Rows
+    /// that have not changed since the last flush are left untouched.
+    pub fn flush(&mut self, output: &mut Output) -> Result {
+        for row in 0..self.rows {
+            if !self.dirty_rows[row] {
+                continue;
+            }
+
+            let start = row * self.columns;
+            let mut column = 0;
+            while column < self.columns {
+                let run_start = column;
+                let attr = (self.cells[start + column].fg, self.cells[start + column].bg);
+
+                while column < self.columns
+                    && (self.cells[start + column].fg, self.cells[start + column].bg) == attr
+                {
+                    column += 1;
+                }
+
+                let mut codes: Vec<u16> = (run_start..column)
+                    .map(|c| u16::from(self.cells[start + c].ch))
+                    .collect();
+                codes.push(0);
+                let text = CStr16::from_u16_with_nul(&codes)
+                    .map_err(|_| Error::new(Status::DEVICE_ERROR, ()))?;
+
+                output.set_cursor_position(run_start, row)?;
+                output.set_color(attr.0, attr.1)?;
+                output.output_string(text)?;
+            }
+
+            self.dirty_rows[row] = false;
+        }
+
+        // The loop above only touches `output`'s cursor/color when a row
+        // needed redrawing, so a cursor move or color change with no
+        // accompanying write (every dirty row still clean) would otherwise
+        // never reach the firmware. Always re-sync both here.
+        output.set_cursor_position(self.cursor.0, self.cursor.1)?;
+        output.set_color(self.fg, self.bg)?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            self.put_char(ch);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+//! Text-based console protocols.
+
+#[cfg(feature = "alloc")]
+mod buffer;
+mod output;
+
+#[cfg(feature = "alloc")]
+pub use buffer::Console;
+pub use output::{AnsiOutput, Color, Output, OutputMode, OutputModeIter};
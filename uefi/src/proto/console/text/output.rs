@@ -1,4 +1,5 @@
 use crate::proto::unsafe_protocol;
+use crate::result::Completion;
 use crate::{CStr16, Char16, Result, ResultExt, Status};
 use core::fmt;
 use core::fmt::{Debug, Formatter};
@@ -72,6 +73,15 @@ impl Output {
         })
     }
 
+    /// Writes a string to the output device, reporting a non-fatal warning
+    /// (such as [`Status::WARN_UNKNOWN_GLYPH`] for an unsupported glyph)
+    /// back to the caller via the returned [`Completion`] instead of
+    /// discarding it, as [`output_string_lossy`](Self::output_string_lossy)
+    /// does.
+    pub fn output_string_checked(&mut self, string: &CStr16) -> Result<Completion<()>> {
+        unsafe { (self.output_string)(self, string.as_ptr()) }.into_with_val_completion(|| ())
+    }
+
     /// Checks if a string contains only supported characters.
     ///
     /// UEFI applications are encouraged to try to print a string even if it contains
@@ -338,7 +348,7 @@ struct OutputData {
 /// All colors can be used as foreground colors.
 /// The first 8 colors can also be used as background colors.
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Color {
     Black = 0,
     Blue,
@@ -357,3 +367,409 @@ pub enum Color {
     Yellow,
     White,
 }
+
+/// Size of the text buffer an [`AnsiOutput`] flushes to [`Output`] between
+/// attribute/cursor operations. Mirrors the buffer in `Output`'s own
+/// `fmt::Write` impl.
+const ANSI_TEXT_BUF_SIZE: usize = 128;
+
+/// Maximum number of `;`-separated CSI parameters an [`AnsiOutput`] will
+/// track; anything beyond this is parsed but dropped.
+const ANSI_MAX_CSI_PARAMS: usize = 8;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AnsiState {
+    Ground,
+    Escape,
+    Csi,
+}
+
+/// Adapts an [`Output`] so that a minimal subset of ANSI/VT escape
+/// sequences written through [`fmt::Write`] are interpreted and mapped onto
+/// [`Output::set_color`], [`Output::set_cursor_position`], and
+/// [`Output::clear`], instead of being dumped to the screen as raw UCS-2.
+///
+/// Supported sequences:
+/// - `ESC [ ... m` (SGR): sets the foreground/background color. Params
+///   30-37/90-97 set the foreground, 40-47/100-107 set the background
+///   (bright background codes are downgraded to their non-bright hue, since
+///   the protocol only allows 8 background colors), and `0` resets to
+///   light gray on black.
+/// - `ESC [ row ; col H` or `f`: moves the cursor (1-based, defaulting to
+///   1,1).
+/// - `ESC [ 2 J`: clears the screen.
+///
+/// Anything else, including a lone `ESC` at the end of the input, is
+/// silently swallowed.
+pub struct AnsiOutput<'a> {
+    output: &'a mut Output,
+    state: AnsiState,
+    params: [u32; ANSI_MAX_CSI_PARAMS],
+    /// Number of CSI parameter slots completed so far (i.e. terminated by a
+    /// `;`), not counting whichever slot is currently being accumulated.
+    param_count: usize,
+    /// Whether the slot at index `param_count` has seen a digit yet. Kept
+    /// separate from `param_count` so that a `;` with no digits before it
+    /// (e.g. the empty first parameter in `\x1b[;5H`) still advances to the
+    /// next slot instead of being folded into it.
+    param_started: bool,
+    /// Set once a `;` arrives after the last available slot
+    /// (`ANSI_MAX_CSI_PARAMS - 1`) has already been used. Further digits are
+    /// dropped instead of being appended onto that last slot, so a 9th+
+    /// parameter can't corrupt the 8th one's already-finished value.
+    overflowed: bool,
+    fg: Color,
+    bg: Color,
+    buf: [u16; ANSI_TEXT_BUF_SIZE + 1],
+    len: usize,
+}
+
+impl<'a> AnsiOutput<'a> {
+    /// Wraps `output` so that escape sequences written to it are
+    /// interpreted rather than printed verbatim.
+    pub fn new(output: &'a mut Output) -> Self {
+        Self {
+            output,
+            state: AnsiState::Ground,
+            params: [0; ANSI_MAX_CSI_PARAMS],
+            param_count: 0,
+            param_started: false,
+            overflowed: false,
+            fg: Color::LightGray,
+            bg: Color::Black,
+            buf: [0; ANSI_TEXT_BUF_SIZE + 1],
+            len: 0,
+        }
+    }
+
+    /// Encodes one decoded character into the pending text buffer,
+    /// flushing it whenever it fills up, translating `\n` to `\r\n` as
+    /// `Output`'s own `fmt::Write` impl does.
+    ///
+    /// Goes through the same `ucs2` encoding path `Output::write_str` uses,
+    /// so a character that doesn't fit in UCS-2 is rejected instead of
+    /// being silently truncated to its low 16 bits.
+    fn push_char(&mut self, ch: char) -> fmt::Result {
+        if ch == '\n' {
+            push_code(&mut *self.output, &mut self.buf, &mut self.len, '\r' as u16)?;
+        }
+
+        let mut utf8_buf = [0u8; 4];
+        let encoded = ch.encode_utf8(&mut utf8_buf);
+
+        let output: &mut Output = &mut *self.output;
+        let buf: &mut [u16] = &mut self.buf;
+        let len: &mut usize = &mut self.len;
+
+        let mut add_code = |code: u16| -> Result<(), ucs2::Error> {
+            push_code(output, buf, len, code).map_err(|_| ucs2::Error::BufferOverflow)
+        };
+
+        ucs2::encode_with(encoded, &mut add_code).map_err(|_| fmt::Error)
+    }
+
+    /// Flushes the pending text buffer to the underlying `Output`, if
+    /// non-empty.
+    fn flush_text(&mut self) -> fmt::Result {
+        flush_text(self.output, &mut self.buf, &mut self.len)
+    }
+
+    /// Dispatches a completed CSI sequence ending in `final_byte`.
+    fn dispatch_csi(&mut self, final_byte: char) -> fmt::Result {
+        // Always flush pending text first, so ordering between text and
+        // attribute/cursor changes is preserved.
+        self.flush_text()?;
+
+        // Include the slot currently being accumulated (if any digit has
+        // reached it) alongside the ones already closed by a `;`.
+        // Once overflowed, every slot (including the last one, which still
+        // holds its own legitimately-parsed value) is in play; otherwise
+        // only the slots seen so far, plus the one currently accumulating.
+        let len = if self.overflowed {
+            ANSI_MAX_CSI_PARAMS
+        } else {
+            self.param_count + usize::from(self.param_started)
+        };
+        let params = &self.params[..len];
+        match final_byte {
+            'm' => {
+                apply_sgr(&mut self.fg, &mut self.bg, params);
+                let _ = self.output.set_color(self.fg, self.bg);
+            }
+            'H' | 'f' => {
+                let row = params.first().copied().unwrap_or(1).max(1);
+                let column = params.get(1).copied().unwrap_or(1).max(1);
+                let _ = self
+                    .output
+                    .set_cursor_position((column - 1) as usize, (row - 1) as usize);
+            }
+            'J' => {
+                if params.first().copied() == Some(2) {
+                    let _ = self.output.clear();
+                }
+            }
+            // Unknown finals are silently swallowed.
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Pushes one UCS-2 code unit into `buf`, then flushes it to `output` if
+/// that made it full.
+fn push_code(output: &mut Output, buf: &mut [u16], len: &mut usize, code: u16) -> fmt::Result {
+    buf[*len] = code;
+    *len += 1;
+
+    if *len == ANSI_TEXT_BUF_SIZE {
+        flush_text(output, buf, len)
+    } else {
+        Ok(())
+    }
+}
+
+/// Flushes the pending text in `buf` to `output`, if non-empty.
+fn flush_text(output: &mut Output, buf: &mut [u16], len: &mut usize) -> fmt::Result {
+    if *len == 0 {
+        return Ok(());
+    }
+
+    buf[*len] = 0;
+    let codes = &buf[..=*len];
+    let text = CStr16::from_u16_with_nul(codes).map_err(|_| fmt::Error)?;
+    let result = output.output_string(text).map_err(|_| fmt::Error);
+    *len = 0;
+    result
+}
+
+impl<'a> fmt::Write for AnsiOutput<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            match self.state {
+                AnsiState::Ground => {
+                    if ch == '\u{1b}' {
+                        self.state = AnsiState::Escape;
+                    } else {
+                        self.push_char(ch)?;
+                    }
+                }
+                AnsiState::Escape => {
+                    if ch == '[' {
+                        self.state = AnsiState::Csi;
+                        self.params = [0; ANSI_MAX_CSI_PARAMS];
+                        self.param_count = 0;
+                        self.param_started = false;
+                        self.overflowed = false;
+                    } else {
+                        // Not a CSI sequence; nothing else is supported.
+                        self.state = AnsiState::Ground;
+                    }
+                }
+                AnsiState::Csi => match ch {
+                    '0'..='9' => {
+                        // Once every slot is spoken for, a 9th+ parameter's
+                        // digits are dropped outright rather than being
+                        // appended onto the 8th slot's already-finished
+                        // value.
+                        if !self.overflowed {
+                            self.param_started = true;
+                            if let Some(p) = self.params.get_mut(self.param_count) {
+                                *p = p
+                                    .saturating_mul(10)
+                                    .saturating_add(ch as u32 - '0' as u32);
+                            }
+                        }
+                    }
+                    ';' => {
+                        // The slot just closed by this `;` counts even if
+                        // it never saw a digit (an empty parameter), so
+                        // advance unconditionally. Once the last slot is
+                        // already in use, stop advancing and mark every
+                        // further parameter as overflow instead.
+                        if self.param_count + 1 < ANSI_MAX_CSI_PARAMS {
+                            self.param_count += 1;
+                            self.param_started = false;
+                        } else {
+                            self.overflowed = true;
+                        }
+                    }
+                    // Other parameter and intermediate bytes (e.g. the `?`
+                    // in `ESC [ ? 25 l`) are skipped, not taken as finals.
+                    '\u{20}'..='\u{2f}' | ':' | '<'..='?' => {}
+                    final_byte => {
+                        self.dispatch_csi(final_byte)?;
+                        self.state = AnsiState::Ground;
+                    }
+                },
+            }
+        }
+
+        // A lone `ESC` (or an unterminated CSI) left pending at the end of
+        // the string is a no-op: it stays in `self.state` until more input
+        // completes or replaces it.
+        self.flush_text()
+    }
+}
+
+/// Applies an SGR (`m`) sequence's parameters to the running `(fg, bg)`
+/// attribute. An empty parameter list is equivalent to a single `0`.
+fn apply_sgr(fg: &mut Color, bg: &mut Color, params: &[u32]) {
+    if params.is_empty() {
+        *fg = Color::LightGray;
+        *bg = Color::Black;
+        return;
+    }
+
+    for &param in params {
+        match param {
+            0 => {
+                *fg = Color::LightGray;
+                *bg = Color::Black;
+            }
+            30..=37 => *fg = ansi_color(param - 30),
+            90..=97 => *fg = ansi_color(param - 90 + 8),
+            40..=47 => *bg = ansi_color(param - 40),
+            // The background can only be one of the first 8 colors; fall
+            // back to the non-bright hue rather than violating that.
+            100..=107 => *bg = clamp_background(ansi_color(param - 100 + 8)),
+            _ => {}
+        }
+    }
+}
+
+/// Maps a 4-bit ANSI color index (0-15, in standard SGR order) onto this
+/// crate's VGA-style [`Color`] enum.
+const fn ansi_color(index: u32) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Brown,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::Yellow,
+        12 => Color::LightBlue,
+        13 => Color::LightMagenta,
+        14 => Color::LightCyan,
+        15 => Color::White,
+        _ => Color::LightGray,
+    }
+}
+
+/// Downgrades `color` to one of the first 8 [`Color`] variants, so it can
+/// be used as a background without tripping [`Output::set_color`]'s
+/// `assert!(bgc < 8)`. Colors that are already legal backgrounds are
+/// returned unchanged; the rest map to their non-bright counterpart.
+pub(crate) const fn clamp_background(color: Color) -> Color {
+    match color {
+        Color::DarkGray => Color::Black,
+        Color::LightBlue => Color::Blue,
+        Color::LightGreen => Color::Green,
+        Color::LightCyan => Color::Cyan,
+        Color::LightRed => Color::Red,
+        Color::LightMagenta => Color::Magenta,
+        Color::Yellow => Color::Brown,
+        Color::White => Color::LightGray,
+        legal => legal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_background_leaves_legal_colors_unchanged() {
+        for color in [
+            Color::Black,
+            Color::Blue,
+            Color::Green,
+            Color::Cyan,
+            Color::Red,
+            Color::Magenta,
+            Color::Brown,
+            Color::LightGray,
+        ] {
+            assert_eq!(clamp_background(color), color);
+        }
+    }
+
+    #[test]
+    fn clamp_background_downgrades_bright_colors() {
+        assert_eq!(clamp_background(Color::DarkGray), Color::Black);
+        assert_eq!(clamp_background(Color::LightBlue), Color::Blue);
+        assert_eq!(clamp_background(Color::LightGreen), Color::Green);
+        assert_eq!(clamp_background(Color::LightCyan), Color::Cyan);
+        assert_eq!(clamp_background(Color::LightRed), Color::Red);
+        assert_eq!(clamp_background(Color::LightMagenta), Color::Magenta);
+        assert_eq!(clamp_background(Color::Yellow), Color::Brown);
+        assert_eq!(clamp_background(Color::White), Color::LightGray);
+    }
+
+    #[test]
+    fn ansi_color_maps_standard_and_bright_indices() {
+        assert_eq!(ansi_color(0), Color::Black);
+        assert_eq!(ansi_color(1), Color::Red);
+        assert_eq!(ansi_color(8), Color::DarkGray);
+        assert_eq!(ansi_color(15), Color::White);
+    }
+
+    #[test]
+    fn ansi_color_falls_back_for_unused_index() {
+        // Index 16+ isn't part of the 16-color SGR table; index 7 (SGR white)
+        // has no arm of its own and reaches LightGray through the same
+        // fallback.
+        assert_eq!(ansi_color(7), Color::LightGray);
+        assert_eq!(ansi_color(16), Color::LightGray);
+    }
+
+    #[test]
+    fn apply_sgr_empty_params_resets() {
+        let mut fg = Color::Red;
+        let mut bg = Color::Blue;
+        apply_sgr(&mut fg, &mut bg, &[]);
+        assert_eq!(fg, Color::LightGray);
+        assert_eq!(bg, Color::Black);
+    }
+
+    #[test]
+    fn apply_sgr_sets_foreground_and_background() {
+        let mut fg = Color::LightGray;
+        let mut bg = Color::Black;
+        apply_sgr(&mut fg, &mut bg, &[31, 44]);
+        assert_eq!(fg, Color::Red);
+        assert_eq!(bg, Color::Blue);
+    }
+
+    #[test]
+    fn apply_sgr_downgrades_bright_background() {
+        let mut fg = Color::LightGray;
+        let mut bg = Color::Black;
+        // 103 is bright-yellow-as-background (SGR 100-107), which has no
+        // legal `Output::set_color` background slot.
+        apply_sgr(&mut fg, &mut bg, &[103]);
+        assert_eq!(bg, Color::Brown);
+    }
+
+    #[test]
+    fn apply_sgr_last_matching_param_wins() {
+        let mut fg = Color::LightGray;
+        let mut bg = Color::Black;
+        apply_sgr(&mut fg, &mut bg, &[31, 32]);
+        assert_eq!(fg, Color::Green);
+    }
+
+    #[test]
+    fn apply_sgr_reset_code_overrides_earlier_params() {
+        let mut fg = Color::LightGray;
+        let mut bg = Color::Black;
+        apply_sgr(&mut fg, &mut bg, &[31, 44, 0]);
+        assert_eq!(fg, Color::LightGray);
+        assert_eq!(bg, Color::Black);
+    }
+}
@@ -56,3 +56,126 @@ impl<Data: Debug> Display for Error<Data> {
 
 #[cfg(feature = "unstable")]
 impl<Data: Debug> core::error::Error for Error<Data> {}
+
+/// A successful UEFI call that still carries a non-fatal warning status
+/// (for example [`Status::WARN_UNKNOWN_GLYPH`]), plus whatever value the
+/// call produced.
+///
+/// Some UEFI protocol calls can partially succeed: the call completes and
+/// returns data, but the status code says something about it wasn't fully
+/// honored. Wrapping that value in a `Completion` lets a caller that cares
+/// inspect the warning via [`status`][Self::status] rather than have it
+/// silently discarded.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Completion<T> {
+    status: Status,
+    data: T,
+}
+
+impl<T> Completion<T> {
+    /// Creates a `Completion` from a status and a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` is an error (see [`Status::is_error`]).
+    pub fn new(status: Status, data: T) -> Self {
+        assert!(!status.is_error());
+        Self { status, data }
+    }
+
+    /// Returns the status, which is either [`Status::SUCCESS`] or a
+    /// `WARN_*` code.
+    pub const fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns a reference to the completion's value.
+    pub const fn value(&self) -> &T {
+        &self.data
+    }
+
+    /// Splits this completion into its status and value.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn split(self) -> (Status, T) {
+        (self.status, self.data)
+    }
+
+    /// Maps the value of a `Completion` while keeping its status.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Completion<U> {
+        Completion::new(self.status, f(self.data))
+    }
+}
+
+impl Status {
+    /// Converts this status into a `Result<Completion<T>>`, calling `f` to
+    /// produce the value on success. Unlike [`Status::into_with_val`], which
+    /// turns a non-fatal `WARN_*` status into an `Err`, a warning here still
+    /// yields `Ok` with the value and is reported via the resulting
+    /// [`Completion`].
+    pub fn into_with_val_completion<T>(
+        self,
+        f: impl FnOnce() -> T,
+    ) -> crate::Result<Completion<T>> {
+        if self.is_error() {
+            Err(Error::new(self, ()))
+        } else {
+            Ok(Completion::new(self, f()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completion_accessors_and_split() {
+        let completion = Completion::new(Status::WARN_UNKNOWN_GLYPH, 42);
+        assert_eq!(completion.status(), Status::WARN_UNKNOWN_GLYPH);
+        assert_eq!(*completion.value(), 42);
+
+        let (status, data) = completion.split();
+        assert_eq!(status, Status::WARN_UNKNOWN_GLYPH);
+        assert_eq!(data, 42);
+    }
+
+    #[test]
+    fn completion_map_keeps_status() {
+        let completion = Completion::new(Status::WARN_UNKNOWN_GLYPH, 42);
+        let mapped = completion.map(|v| v * 2);
+        assert_eq!(mapped.status(), Status::WARN_UNKNOWN_GLYPH);
+        assert_eq!(*mapped.value(), 84);
+    }
+
+    #[test]
+    #[should_panic]
+    fn completion_new_panics_on_error_status() {
+        let _ = Completion::new(Status::DEVICE_ERROR, ());
+    }
+
+    #[test]
+    fn into_with_val_completion_reports_success() {
+        let completion = Status::SUCCESS
+            .into_with_val_completion(|| "ok")
+            .expect("SUCCESS should not produce an error");
+        assert_eq!(completion.status(), Status::SUCCESS);
+        assert_eq!(*completion.value(), "ok");
+    }
+
+    #[test]
+    fn into_with_val_completion_reports_warning() {
+        let completion = Status::WARN_UNKNOWN_GLYPH
+            .into_with_val_completion(|| "partial")
+            .expect("a warning should still produce a value");
+        assert_eq!(completion.status(), Status::WARN_UNKNOWN_GLYPH);
+        assert_eq!(*completion.value(), "partial");
+    }
+
+    #[test]
+    fn into_with_val_completion_reports_error() {
+        let err = Status::DEVICE_ERROR
+            .into_with_val_completion(|| "unused")
+            .unwrap_err();
+        assert_eq!(err.status(), Status::DEVICE_ERROR);
+    }
+}
@@ -0,0 +1,46 @@
+//! Command-line argument access, decoded from [`LoadedImage`]'s
+//! `load_options`.
+
+use crate::data_types::CString16;
+use crate::proto::loaded_image::LoadedImage;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Returns this image's command-line arguments.
+///
+/// UEFI hands the whole command line to an image as a single UCS-2
+/// string (or not at all, if the image was launched without one); this
+/// splits it the same way a Unix `argv` would be, on whitespace, with no
+/// quoting rules. Returns an empty iterator if the image has no load
+/// options, or they aren't valid UCS-2 text.
+#[must_use]
+pub fn args(loaded_image: &LoadedImage) -> Args {
+    let words = loaded_image
+        .load_options_as_cstr16()
+        .ok()
+        .map(|options| {
+            let text: String = options.iter().map(char::from).collect();
+            text.split_whitespace()
+                .filter_map(|word| CString16::try_from(word).ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    Args {
+        words: words.into_iter(),
+    }
+}
+
+/// Iterator over an image's command-line arguments. See [`args`].
+#[derive(Debug)]
+pub struct Args {
+    words: alloc::vec::IntoIter<CString16>,
+}
+
+impl Iterator for Args {
+    type Item = CString16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.words.next()
+    }
+}
@@ -0,0 +1,104 @@
+//! Environment-variable access, layered over [`RuntimeServices`] variable
+//! storage under a private vendor GUID so these don't collide with
+//! firmware- or other-application-owned variables.
+//!
+//! [`RuntimeServices`]: crate::table::runtime::RuntimeServices
+
+use crate::data_types::CString16;
+use crate::table::runtime::{VariableAttributes, VariableVendor};
+use crate::table::runtime::RuntimeServices;
+use crate::{CStr16, Guid, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Vendor GUID under which every environment variable set through this
+/// module is stored.
+pub const ENV_VENDOR_GUID: VariableVendor = VariableVendor(Guid::from_bytes([
+    0x5f, 0x2e, 0x2c, 0x5b, 0x6a, 0x3d, 0x4b, 0x1e, 0x9a, 0x0b, 0x9c, 0x41, 0x3e, 0x2b, 0x4d, 0x8f,
+]));
+
+const ATTRIBUTES: VariableAttributes =
+    VariableAttributes::BOOTSERVICE_ACCESS.union(VariableAttributes::RUNTIME_ACCESS);
+
+/// Returns the value of environment variable `name`, if set.
+#[must_use]
+pub fn get(runtime_services: &RuntimeServices, name: &CStr16) -> Option<Vec<u8>> {
+    let size = runtime_services.get_variable_size(name, &ENV_VENDOR_GUID).ok()?;
+    let mut buf = vec![0u8; size];
+    runtime_services
+        .get_variable(name, &ENV_VENDOR_GUID, &mut buf)
+        .ok()?;
+    Some(buf)
+}
+
+/// Sets environment variable `name` to `value`, creating it if it doesn't
+/// already exist.
+pub fn set(runtime_services: &RuntimeServices, name: &CStr16, value: &[u8]) -> Result {
+    runtime_services.set_variable(name, &ENV_VENDOR_GUID, ATTRIBUTES, value)
+}
+
+/// Removes environment variable `name`, if set.
+///
+/// Per the `GetVariable`/`SetVariable` contract, removing a variable is
+/// done by setting it with no attributes and no data.
+pub fn remove(runtime_services: &RuntimeServices, name: &CStr16) -> Result {
+    runtime_services.set_variable(name, &ENV_VENDOR_GUID, VariableAttributes::empty(), &[])
+}
+
+/// Returns an iterator over the names of every environment variable set
+/// through this module.
+pub fn vars(runtime_services: &RuntimeServices) -> Vars<'_> {
+    Vars {
+        runtime_services,
+        name: CString16::new(),
+        // `GetNextVariableName`'s first call is defined to ignore the
+        // vendor argument, so any value works as the starting cursor.
+        vendor: VariableVendor(Guid::from_bytes([0; 16])),
+        done: false,
+    }
+}
+
+/// Iterator over environment variable names. See [`vars`].
+#[derive(Debug)]
+pub struct Vars<'a> {
+    runtime_services: &'a RuntimeServices,
+    name: CString16,
+    vendor: VariableVendor,
+    done: bool,
+}
+
+impl<'a> Iterator for Vars<'a> {
+    type Item = CString16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // Keep walking `GetNextVariableName` past variables that belong to
+        // other vendors; the firmware enumerates all of them, not just
+        // ours. `(name, vendor)` together are the enumeration cursor, so
+        // both must be carried forward exactly as returned, not just the
+        // name, or the next call doesn't match any entry in firmware's
+        // internal walk and is rejected as `Err` (which would otherwise
+        // look identical to "enumeration finished").
+        loop {
+            match self
+                .runtime_services
+                .get_next_variable_name(&self.name, &self.vendor)
+            {
+                Ok((name, vendor)) => {
+                    self.name = name.clone();
+                    self.vendor = vendor;
+                    if vendor == ENV_VENDOR_GUID {
+                        return Some(name);
+                    }
+                }
+                Err(_) => {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+    }
+}
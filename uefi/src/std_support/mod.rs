@@ -0,0 +1,13 @@
+//! Building blocks for a minimal `std` on top of UEFI.
+//!
+//! The upstream `*-unknown-uefi` `std` port needs safe, testable access to
+//! command-line arguments, environment variables, and a way to hand
+//! control back to firmware on exit. Rather than have that logic live
+//! inside libstd's `sys/uefi` backend, it's exposed here as ordinary APIs:
+//! the `std` port can depend on a stable surface, and `#![no_std]`
+//! applications that want the same argument/environment plumbing can use
+//! it directly.
+
+pub mod args;
+pub mod env;
+pub mod process;
@@ -0,0 +1,30 @@
+//! A `std::process::exit`-style path back to firmware.
+
+use crate::table::boot::BootServices;
+use crate::table::runtime::ResetType;
+use crate::Status;
+
+/// Exits the running image via [`BootServices::exit`], handing control
+/// (and this image's resources) back to firmware.
+///
+/// Mirrors `std::process::exit`: it never returns.
+pub fn exit(boot_services: &BootServices, status: Status) -> ! {
+    // SAFETY: we are intentionally terminating this image; nothing here
+    // runs again afterwards to violate.
+    unsafe {
+        boot_services.exit(
+            boot_services.image_handle(),
+            status,
+            0,
+            core::ptr::null_mut(),
+        )
+    }
+}
+
+/// Shuts the platform down (`ResetSystem` with [`ResetType::SHUTDOWN`]) via
+/// [`RuntimeServices`][crate::table::runtime::RuntimeServices],
+/// for the rare case where boot services have already been exited and
+/// [`exit`] is no longer available.
+pub fn reset(runtime_services: &crate::table::runtime::RuntimeServices, status: Status) -> ! {
+    runtime_services.reset(ResetType::SHUTDOWN, status, None)
+}
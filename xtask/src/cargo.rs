@@ -0,0 +1,210 @@
+//! Helpers for building `cargo` invocations.
+
+use crate::arch::UefiArch;
+use anyhow::Result;
+use std::process::Command;
+
+/// A UEFI package in this workspace.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Package {
+    Uefi,
+    UefiMacros,
+    UefiServices,
+    UefiTestRunner,
+    Xtask,
+}
+
+impl Package {
+    /// All packages except `xtask` itself.
+    pub fn all_except_xtask() -> Vec<Self> {
+        vec![
+            Self::Uefi,
+            Self::UefiMacros,
+            Self::UefiServices,
+            Self::UefiTestRunner,
+        ]
+    }
+
+    /// Packages that get published to crates.io.
+    pub fn published() -> Vec<Self> {
+        vec![Self::Uefi, Self::UefiMacros, Self::UefiServices]
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Uefi => "uefi",
+            Self::UefiMacros => "uefi-macros",
+            Self::UefiServices => "uefi-services",
+            Self::UefiTestRunner => "uefi-test-runner",
+            Self::Xtask => "xtask",
+        }
+    }
+}
+
+/// A cargo feature flag that can be passed to one of this workspace's
+/// packages.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Feature {
+    Alloc,
+    Exts,
+    Logger,
+    Pxe,
+    TpmV1,
+    TpmV2,
+    MultiProcessor,
+    TestUnstable,
+    /// Exercises the Graphics Output Protocol against whichever display
+    /// device `xtask run --graphics-device` asked QEMU for.
+    Gop,
+}
+
+impl Feature {
+    /// The features available on `package`, used to build every feature
+    /// permutation for `xtask build --feature-permutations`.
+    pub fn package_features(package: Package) -> &'static [Self] {
+        match package {
+            Package::Uefi => &[Self::Alloc, Self::Exts, Self::Logger],
+            Package::UefiServices => &[Self::Logger],
+            _ => &[],
+        }
+    }
+
+    /// The standard feature set enabled for most builds: `alloc` and
+    /// `exts`, plus `logger` when `with_logger` is set, plus `unstable`
+    /// when `unstable` is set.
+    pub fn more_code(unstable: bool, with_logger: bool) -> Vec<Self> {
+        let mut features = vec![Self::Alloc, Self::Exts];
+        if with_logger {
+            features.push(Self::Logger);
+        }
+        if unstable {
+            features.push(Self::TestUnstable);
+        }
+        features
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Alloc => "alloc",
+            Self::Exts => "exts",
+            Self::Logger => "logger",
+            Self::Pxe => "pxe",
+            Self::TpmV1 => "tpm_v1",
+            Self::TpmV2 => "tpm_v2",
+            Self::MultiProcessor => "multi_processor",
+            Self::TestUnstable => "unstable",
+            Self::Gop => "gop",
+        }
+    }
+}
+
+/// Which of a package's targets to build/test/lint.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TargetTypes {
+    /// Only the default targets cargo would otherwise pick.
+    Default,
+    /// Binaries, examples, and the lib target.
+    BinsExamplesLib,
+    /// Binaries and examples, but not the lib target.
+    BinsExamples,
+}
+
+/// What to do with the packages/features selected by a [`Cargo`] invocation.
+#[derive(Debug, Clone, Copy)]
+pub enum CargoAction {
+    Build,
+    Clippy,
+    Doc {
+        open: bool,
+        document_private_items: bool,
+    },
+    Miri,
+    Test,
+}
+
+/// Describes a `cargo` invocation to run via [`Cargo::command`].
+#[derive(Debug, Clone)]
+pub struct Cargo {
+    pub action: CargoAction,
+    pub features: Vec<Feature>,
+    pub packages: Vec<Package>,
+    pub release: bool,
+    pub target: Option<UefiArch>,
+    pub warnings_as_errors: bool,
+    pub target_types: TargetTypes,
+}
+
+impl Cargo {
+    /// Builds the `cargo` command described by `self`.
+    pub fn command(&self) -> Result<Command> {
+        let mut cmd = Command::new("cargo");
+
+        cmd.arg(match self.action {
+            CargoAction::Build => "build",
+            CargoAction::Clippy => "clippy",
+            CargoAction::Doc { .. } => "doc",
+            CargoAction::Miri => "miri",
+            CargoAction::Test => "test",
+        });
+
+        if let CargoAction::Miri = self.action {
+            cmd.arg("test");
+        }
+
+        for package in &self.packages {
+            cmd.args(["--package", package.name()]);
+        }
+
+        match self.target_types {
+            TargetTypes::Default => {}
+            TargetTypes::BinsExamplesLib => {
+                cmd.args(["--bins", "--examples", "--lib"]);
+            }
+            TargetTypes::BinsExamples => {
+                cmd.args(["--bins", "--examples"]);
+            }
+        }
+
+        if !self.features.is_empty() {
+            let features = self
+                .features
+                .iter()
+                .map(|feature| feature.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            cmd.args(["--no-default-features", "--features", &features]);
+        }
+
+        if self.release {
+            cmd.arg("--release");
+        }
+
+        if let Some(target) = self.target {
+            let triple = match target {
+                UefiArch::X86_64 => "x86_64-unknown-uefi",
+                UefiArch::AArch64 => "aarch64-unknown-uefi",
+                UefiArch::Ia32 => "i686-unknown-uefi",
+            };
+            cmd.args(["--target", triple]);
+        }
+
+        if self.warnings_as_errors {
+            cmd.env("RUSTFLAGS", "-D warnings");
+        }
+
+        if let CargoAction::Doc {
+            open,
+            document_private_items,
+        } = self.action
+        {
+            if open {
+                cmd.arg("--open");
+            }
+            if document_private_items {
+                cmd.arg("--document-private-items");
+            }
+        }
+
+        Ok(cmd)
+    }
+}
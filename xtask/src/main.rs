@@ -16,7 +16,7 @@ use arch::UefiArch;
 use cargo::{Cargo, CargoAction, Feature, Package, TargetTypes};
 use clap::Parser;
 use itertools::Itertools;
-use opt::{Action, BuildOpt, ClippyOpt, DocOpt, Opt, QemuOpt, TpmVersion};
+use opt::{Action, BuildOpt, ClippyOpt, DocOpt, GraphicsDevice, Opt, QemuOpt, TpmVersion};
 use util::run_cmd;
 
 fn build_feature_permutations(opt: &BuildOpt) -> Result<()> {
@@ -143,6 +143,12 @@ fn run_vm_tests(opt: &QemuOpt) -> Result<()> {
         features.push(Feature::TestUnstable);
     }
 
+    // Enable the GOP test if a display device was requested; not every CI
+    // runner can render a framebuffer, so this stays opt-in.
+    if opt.graphics_device != GraphicsDevice::None {
+        features.push(Feature::Gop);
+    }
+
     // Build uefi-test-runner.
     let cargo = Cargo {
         action: CargoAction::Build,
@@ -0,0 +1,224 @@
+//! Command line options for xtask.
+
+use crate::arch::UefiArch;
+use clap::{Parser, Subcommand, ValueEnum};
+use core::ops::Deref;
+use core::str::FromStr;
+
+#[derive(Debug, Parser)]
+pub struct Opt {
+    #[clap(subcommand)]
+    pub action: Action,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Action {
+    /// Build all the UEFI packages.
+    Build(BuildOpt),
+    /// Run clippy on all the UEFI packages.
+    Clippy(ClippyOpt),
+    /// Build the documentation.
+    Doc(DocOpt),
+    /// Regenerate the device path code.
+    GenCode(GenCodeOpt),
+    /// Run the unit tests and doctests under Miri.
+    Miri(MiriOpt),
+    /// Build uefi-test-runner and run it in QEMU.
+    Run(QemuOpt),
+    /// Run the unit tests and doctests on the host.
+    Test(TestOpt),
+}
+
+#[derive(Debug, Parser)]
+pub struct GenCodeOpt {}
+
+#[derive(Debug, Parser)]
+pub struct MiriOpt {}
+
+#[derive(Debug, Parser)]
+pub struct TargetOpt {
+    /// Target to build for.
+    #[clap(long, default_value = "x86_64")]
+    pub target: UefiArch,
+}
+
+impl Deref for TargetOpt {
+    type Target = UefiArch;
+
+    fn deref(&self) -> &Self::Target {
+        &self.target
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct BuildModeOpt {
+    /// Build in release mode.
+    #[clap(long)]
+    pub release: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct WarningOpt {
+    /// Treat warnings as errors.
+    #[clap(long)]
+    pub warnings_as_errors: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct UnstableOpt {
+    /// Enable unstable features.
+    #[clap(long)]
+    pub unstable: bool,
+}
+
+impl Deref for UnstableOpt {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.unstable
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct BuildOpt {
+    #[clap(flatten)]
+    pub target: TargetOpt,
+    #[clap(flatten)]
+    pub build_mode: BuildModeOpt,
+    /// Build every permutation of feature flags.
+    #[clap(long)]
+    pub feature_permutations: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ClippyOpt {
+    #[clap(flatten)]
+    pub target: TargetOpt,
+    #[clap(flatten)]
+    pub warning: WarningOpt,
+}
+
+#[derive(Debug, Parser)]
+pub struct DocOpt {
+    #[clap(flatten)]
+    pub warning: WarningOpt,
+    #[clap(flatten)]
+    pub unstable: UnstableOpt,
+    /// Open the docs in a browser after building them.
+    #[clap(long)]
+    pub open: bool,
+    /// Include private items in the docs.
+    #[clap(long)]
+    pub document_private_items: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct TestOpt {
+    /// Skip the uefi-macros tests.
+    #[clap(long)]
+    pub skip_macro_tests: bool,
+    #[clap(flatten)]
+    pub unstable: UnstableOpt,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub enum TpmVersion {
+    V1,
+    V2,
+}
+
+/// QEMU display adapter used to back the Graphics Output Protocol.
+///
+/// Mirrors the adapter choices real UEFI projects test against, so the GOP
+/// framebuffer path gets exercised against more than one firmware
+/// implementation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum GraphicsDevice {
+    /// The standard VGA framebuffer (`-vga std`).
+    StdVga,
+    /// `virtio-gpu-pci`, the virtio GPU device.
+    VirtioGpuPci,
+    /// `vmware-svga`, the VMware SVGA II adapter.
+    VmwareSvga,
+    /// No display device; the GOP test is disabled. The default, since not
+    /// every CI runner can render a framebuffer.
+    None,
+}
+
+#[derive(Debug, Parser)]
+pub struct QemuOpt {
+    #[clap(flatten)]
+    pub target: TargetOpt,
+    #[clap(flatten)]
+    pub build_mode: BuildModeOpt,
+    #[clap(flatten)]
+    pub unstable: UnstableOpt,
+    /// Disable the virtual packet network.
+    #[clap(long)]
+    pub disable_network: bool,
+    /// TPM device to expose to the VM.
+    #[clap(long, value_enum)]
+    pub tpm: Option<TpmVersion>,
+    /// Set when running under CI; disables KVM-dependent tests.
+    #[clap(long)]
+    pub ci: bool,
+    /// Display adapter to back the Graphics Output Protocol tests.
+    #[clap(long, value_enum, default_value = "none")]
+    pub graphics_device: GraphicsDevice,
+    /// Framebuffer resolution requested from the graphics device, e.g.
+    /// `1024x768`. Only meaningful when `--graphics-device` isn't `none`.
+    #[clap(long, default_value = "800x600")]
+    pub graphics_resolution: Resolution,
+}
+
+/// A `WIDTHxHEIGHT` framebuffer resolution, e.g. `1024x768`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s
+            .split_once('x')
+            .ok_or_else(|| format!("expected WIDTHxHEIGHT (e.g. 1024x768), got `{s}`"))?;
+        let width = width
+            .parse()
+            .map_err(|_| format!("invalid width in `{s}`"))?;
+        let height = height
+            .parse()
+            .map_err(|_| format!("invalid height in `{s}`"))?;
+        Ok(Self { width, height })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_parses_valid_input() {
+        let resolution: Resolution = "1024x768".parse().unwrap();
+        assert_eq!(
+            resolution,
+            Resolution {
+                width: 1024,
+                height: 768
+            }
+        );
+    }
+
+    #[test]
+    fn resolution_rejects_missing_separator() {
+        assert!("1024768".parse::<Resolution>().is_err());
+    }
+
+    #[test]
+    fn resolution_rejects_non_numeric_dimensions() {
+        assert!("abcx768".parse::<Resolution>().is_err());
+        assert!("1024xdef".parse::<Resolution>().is_err());
+    }
+}
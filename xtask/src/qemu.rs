@@ -0,0 +1,110 @@
+//! QEMU invocation for VM tests.
+
+use crate::arch::UefiArch;
+use crate::opt::{GraphicsDevice, QemuOpt, Resolution};
+use crate::util::run_cmd;
+use anyhow::Result;
+use std::process::Command;
+
+/// Returns the `-device`/`-vga`/`-g` flags needed to back the Graphics
+/// Output Protocol with `device` at `resolution`, or `None` if no display
+/// device should be added at all.
+fn graphics_device_args(device: GraphicsDevice, resolution: Resolution) -> Option<Vec<String>> {
+    let Resolution { width, height } = resolution;
+
+    match device {
+        GraphicsDevice::None => None,
+        // `-g` is QEMU's global "preferred resolution" option; `std-vga`
+        // and `vmware-svga` both honor it instead of taking a resolution
+        // property directly.
+        GraphicsDevice::StdVga => Some(vec![
+            "-vga".into(),
+            "std".into(),
+            "-g".into(),
+            format!("{width}x{height}x32"),
+        ]),
+        GraphicsDevice::VirtioGpuPci => Some(vec![
+            "-device".into(),
+            format!("virtio-gpu-pci,xres={width},yres={height}"),
+        ]),
+        GraphicsDevice::VmwareSvga => Some(vec![
+            "-device".into(),
+            "vmware-svga".into(),
+            "-g".into(),
+            format!("{width}x{height}x32"),
+        ]),
+    }
+}
+
+fn qemu_binary(target: UefiArch) -> &'static str {
+    match target {
+        UefiArch::X86_64 => "qemu-system-x86_64",
+        UefiArch::AArch64 => "qemu-system-aarch64",
+        UefiArch::Ia32 => "qemu-system-i386",
+    }
+}
+
+/// Builds and runs the QEMU command for the VM tests, adding the
+/// display-adapter flags from `opt`. No networking, TPM, or multiprocessor
+/// flags are added to the command.
+pub fn run_qemu(target: UefiArch, opt: &QemuOpt) -> Result<()> {
+    let mut cmd = Command::new(qemu_binary(target));
+
+    if let Some(args) = graphics_device_args(opt.graphics_device, opt.graphics_resolution) {
+        cmd.args(args);
+    }
+
+    run_cmd(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: Resolution = Resolution {
+        width: 1024,
+        height: 768,
+    };
+
+    #[test]
+    fn none_adds_no_display_device() {
+        assert_eq!(graphics_device_args(GraphicsDevice::None, RESOLUTION), None);
+    }
+
+    #[test]
+    fn std_vga_uses_the_g_option() {
+        assert_eq!(
+            graphics_device_args(GraphicsDevice::StdVga, RESOLUTION),
+            Some(vec![
+                "-vga".into(),
+                "std".into(),
+                "-g".into(),
+                "1024x768x32".into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn virtio_gpu_pci_takes_resolution_as_device_properties() {
+        assert_eq!(
+            graphics_device_args(GraphicsDevice::VirtioGpuPci, RESOLUTION),
+            Some(vec![
+                "-device".into(),
+                "virtio-gpu-pci,xres=1024,yres=768".into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn vmware_svga_uses_the_g_option() {
+        assert_eq!(
+            graphics_device_args(GraphicsDevice::VmwareSvga, RESOLUTION),
+            Some(vec![
+                "-device".into(),
+                "vmware-svga".into(),
+                "-g".into(),
+                "1024x768x32".into(),
+            ])
+        );
+    }
+}